@@ -0,0 +1,171 @@
+//! Deserializing Rust values directly out of an RDF graph.
+//!
+//! This promotes the hand-written experiment from issue 5
+//! (manually walking `graph.iter_for_sp(subject, &predicate)` for every field)
+//! into a derivable [`FromGraph`] trait: annotate a struct with
+//! `#[derive(FromGraph)]`, tag each field with `#[rdf(predicate = "...")]`,
+//! and the companion `sophia_derive` macro generates the traversal for you.
+//!
+//! ```ignore
+//! #[derive(FromGraph)]
+//! struct A {
+//!     #[rdf(predicate = "http://www.w3.org/1999/02/22-rdf-syntax-ns#value")]
+//!     value: i32,
+//! }
+//!
+//! #[derive(FromGraph)]
+//! struct B {
+//!     #[rdf(predicate = "http://ex.co/ns/has_a", nested)]
+//!     a: A,
+//!     #[rdf(predicate = "http://www.w3.org/1999/02/22-rdf-syntax-ns#value")]
+//!     value: i32,
+//! }
+//! ```
+//!
+//! [`FromGraph`]: trait.FromGraph.html
+
+use std::borrow::Borrow;
+use std::error::Error as StdError;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{CoercibleWith, Never, SophiaError};
+use crate::graph::Graph;
+use crate::term::Term;
+
+/// A trait alias for types which are appropriate as a graph's associated error type.
+///
+/// Carried over from the original `FromGraph` experiment, which bounded this
+/// the same way: `CoercibleWith<Never> + CoercibleWith<SophiaError>`.
+pub trait GraphError: CoercibleWith<Never> + CoercibleWith<SophiaError> {}
+impl<E> GraphError for E where E: CoercibleWith<Never> + CoercibleWith<SophiaError> {}
+
+/// A convenient trait alias, easier to use than `Graph` itself.
+///
+/// As the name implies, the Higher-Ranked Trait Bound requires the graph to be
+/// valid for *every* lifetime, so it cannot borrow anything that would
+/// otherwise restrict its own lifetime.
+pub trait OwnedGraph<E>: for<'x> Graph<'x, Error = E>
+where
+    E: GraphError,
+{
+}
+impl<G, E> OwnedGraph<E> for G
+where
+    G: for<'x> Graph<'x, Error = E>,
+    E: GraphError,
+{
+}
+
+/// A type that can be deserialized out of an RDF [`Graph`](../graph/trait.Graph.html),
+/// by reading the triples whose subject is a given node.
+///
+/// This trait is rarely implemented by hand: derive it instead with
+/// `#[derive(FromGraph)]`. Each field is annotated with
+/// `#[rdf(predicate = "...")]` to say which predicate holds its value:
+///
+/// * scalar fields are parsed from the object's lexical value via [`FromStr`];
+/// * fields whose type is itself `FromGraph` are recursed into,
+///   using the `nested` flag: `#[rdf(predicate = "...", nested)]`;
+/// * `Option<T>` fields tolerate zero matching triples;
+/// * `Vec<T>` fields collect every matching triple.
+///
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+pub trait FromGraph<Td, E>: Sized
+where
+    Td: Borrow<str>,
+    E: GraphError,
+{
+    /// Build `Self` by reading the triples of `graph` whose subject is `subject`.
+    fn from_graph<G>(subject: &Term<Td>, graph: &G) -> Result<Self, FromGraphError>
+    where
+        G: OwnedGraph<E>;
+}
+
+/// A scalar value parsed from the lexical form of a single RDF term.
+///
+/// The code generated by `#[derive(FromGraph)]` calls this trait for every
+/// field that is not flagged `nested`; it is not meant to be implemented by hand.
+pub trait FromGraphTerm<Td>: Sized
+where
+    Td: Borrow<str>,
+{
+    /// Parse `Self` from the lexical value of `term`.
+    fn from_graph_term(term: &Term<Td>) -> Result<Self, FromGraphError>;
+}
+
+impl<V, Td> FromGraphTerm<Td> for V
+where
+    V: FromStr,
+    V::Err: StdError + 'static,
+    Td: Borrow<str>,
+{
+    fn from_graph_term(term: &Term<Td>) -> Result<Self, FromGraphError> {
+        term.value()
+            .parse()
+            .map_err(|e: V::Err| FromGraphError::ParseError(Box::new(e)))
+    }
+}
+
+/// The error type returned by [`FromGraph::from_graph`](trait.FromGraph.html#tymethod.from_graph)
+/// and by the code generated by `#[derive(FromGraph)]`.
+#[derive(Debug)]
+pub enum FromGraphError {
+    /// No triple was found for a required (non-`Option`) field.
+    MissingTriple {
+        /// The predicate that was expected to match at least one triple.
+        predicate: String,
+    },
+    /// A literal object could not be parsed into the field's scalar type.
+    ParseError(Box<dyn StdError>),
+    /// The underlying [`Graph`](../graph/trait.Graph.html) raised an error while iterating.
+    GraphError(Box<dyn StdError>),
+}
+
+impl fmt::Display for FromGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromGraphError::MissingTriple { predicate } => {
+                write!(f, "no triple found for predicate <{}>", predicate)
+            }
+            FromGraphError::ParseError(e) => write!(f, "{}", e),
+            FromGraphError::GraphError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl StdError for FromGraphError {}
+
+/// Turn the `Option<Result<T, E>>` yielded by `graph.iter_for_sp(...).next()`
+/// into a single required value, a [`FromGraphError::MissingTriple`](enum.FromGraphError.html#variant.MissingTriple)
+/// if there was none, or a [`FromGraphError::GraphError`](enum.FromGraphError.html#variant.GraphError)
+/// if the graph itself failed while iterating.
+///
+/// Used by the code generated by `#[derive(FromGraph)]`; not meant to be called directly.
+pub fn required<T, E>(found: Option<Result<T, E>>, predicate: &str) -> Result<T, FromGraphError>
+where
+    E: GraphError + StdError + 'static,
+{
+    match found {
+        None => Err(FromGraphError::MissingTriple {
+            predicate: predicate.to_string(),
+        }),
+        Some(Ok(t)) => Ok(t),
+        Some(Err(e)) => Err(FromGraphError::GraphError(Box::new(e))),
+    }
+}
+
+/// Turn the `Result<T, E>` items yielded by `graph.iter_for_sp(...)` into
+/// `Result<T, FromGraphError>`, for a `Vec<T>` field that accepts any number
+/// of matching triples: a failure from the graph itself is reported as a
+/// [`FromGraphError::GraphError`](enum.FromGraphError.html#variant.GraphError)
+/// rather than abandoned partway through.
+///
+/// Used by the code generated by `#[derive(FromGraph)]`; not meant to be called directly.
+pub fn many<I, T, E>(found: I) -> impl Iterator<Item = Result<T, FromGraphError>>
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: GraphError + StdError + 'static,
+{
+    found.map(|item| item.map_err(|e| FromGraphError::GraphError(Box::new(e))))
+}
@@ -0,0 +1,339 @@
+//! `QuadSource` and `QuadSink`,
+//! are pervasive traits for streaming quads from one object to another.
+//!
+//! They play the same role for [datasets] as
+//! [`TripleSource`] and [`TripleSink`] play for graphs,
+//! so that formats able to represent named graphs (TriG, N-Quads...)
+//! have somewhere to stream into and out of.
+//!
+//! See [`QuadSource`]'s and [`QuadSink`]'s documentation for more detail.
+//!
+//! [datasets]: ../../dataset/index.html
+//! [`QuadSource`]: trait.QuadSource.html
+//! [`QuadSink`]: trait.QuadSink.html
+//! [`TripleSource`]: ../../triple/stream/trait.TripleSource.html
+//! [`TripleSink`]: ../../triple/stream/trait.TripleSink.html
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::iter::Map;
+
+use crate::dataset::*;
+use crate::quad::*;
+use crate::term::*;
+use crate::triple::stream::{SinkError, SourceError, StreamError, StreamResult, TripleSink, TripleSource};
+use crate::triple::Triple;
+
+/// A quad source produces [quads], and may also fail in the process.
+///
+/// It provides methods dedicated to interacting with [`QuadSink`]s.
+/// Any iterator yielding [quads] wrapped in [results]
+/// implements the `QuadSource` trait.
+///
+/// [quads]: ../trait.Quad.html
+/// [results]: ../../error/type.Result.html
+/// [`QuadSink`]: trait.QuadSink.html
+pub trait QuadSource {
+    /// The type of errors produced by this source.
+    type Error: 'static + Error;
+
+    /// Feed all quads from this source into the given [sink](trait.QuadSink.html).
+    ///
+    /// Stop on the first error (in the source or the sink).
+    fn in_sink<QS: QuadSink>(
+        &mut self,
+        sink: &mut QS,
+    ) -> Result<QS::Outcome, StreamError<Self::Error, QS::Error>>;
+
+    /// Insert all quads from this source into the given [dataset](../../dataset/trait.MutableDataset.html).
+    ///
+    /// Stop on the first error (in the source or in the dataset).
+    fn in_dataset<D: MutableDataset>(
+        &mut self,
+        dataset: &mut D,
+    ) -> Result<usize, StreamError<Self::Error, <D as MutableDataset>::MutationError>> {
+        self.in_sink(&mut dataset.inserter())
+    }
+}
+
+impl<I, Q, E> QuadSource for I
+where
+    I: Iterator<Item = Result<Q, E>>,
+    Q: Quad,
+    E: 'static + Error,
+{
+    type Error = E;
+
+    fn in_sink<QS: QuadSink>(
+        &mut self,
+        sink: &mut QS,
+    ) -> Result<QS::Outcome, StreamError<Self::Error, QS::Error>> {
+        for qr in self {
+            let q = qr.map_err(SourceError)?;
+            sink.feed(&q).map_err(SinkError)?;
+        }
+        Ok(sink.finish().map_err(SinkError)?)
+    }
+}
+
+pub type AsInfallibleQuadSource<I, Q> = Map<I, fn(Q) -> Result<Q, Infallible>>;
+
+/// A utility extension trait for converting any iterator of [`Quad`]s
+/// into [`QuadSource`], by wrapping its items in `Ok` results.
+///
+/// [`QuadSource`]: trait.QuadSource.html
+/// [`Quad`]: ../trait.Quad.html
+pub trait AsQuadSource<Q>: Sized {
+    /// Map all items of this iterator into an Ok result.
+    fn as_quad_source(self) -> AsInfallibleQuadSource<Self, Q>;
+}
+
+impl<Q, I> AsQuadSource<Q> for I
+where
+    I: Iterator<Item = Q> + Sized,
+    Q: Quad,
+{
+    fn as_quad_source(self) -> AsInfallibleQuadSource<Self, Q> {
+        self.map(Ok)
+    }
+}
+
+/// A quad sink consumes [quads](../trait.Quad.html),
+/// produces a result, and may also fail in the process.
+///
+/// Typical quad sinks are dataset serializers
+/// or datasets' [inserters] and [removers].
+///
+/// See also [`QuadSource`].
+///
+/// [inserters]: ../../dataset/trait.MutableDataset.html#method.inserter
+/// [removers]: ../../dataset/trait.MutableDataset.html#method.remover
+/// [`QuadSource`]: trait.QuadSource.html
+pub trait QuadSink {
+    /// The type of the result produced by this quad sink.
+    ///
+    /// See [`finish`](#tymethod.finish).
+    type Outcome;
+
+    /// The type of error raised by this quad sink.
+    type Error: 'static + Error;
+
+    /// Feed one quad in this sink.
+    fn feed<Q: Quad>(&mut self, q: &Q) -> Result<(), Self::Error>;
+
+    /// Produce the result once all quads were fed.
+    ///
+    /// NB: the behaviour of a quad sink after `finish` is called is unspecified by this trait.
+    fn finish(&mut self) -> Result<Self::Outcome, Self::Error>;
+}
+
+/// [`()`](https://doc.rust-lang.org/std/primitive.unit.html) acts as a "black hole",
+/// consuming all quads without erring, and producing no result.
+///
+/// Useful for benchmarking quad sources.
+impl QuadSink for () {
+    type Outcome = ();
+    type Error = Infallible;
+
+    fn feed<Q: Quad>(&mut self, _: &Q) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn finish(&mut self) -> Result<Self::Outcome, Self::Error> {
+        Ok(())
+    }
+}
+
+/// Extends any [`TripleSource`](../../triple/stream/trait.TripleSource.html)
+/// with a method to view it as a [`QuadSource`](trait.QuadSource.html),
+/// placing every triple it yields in the default graph (i.e. with no graph name).
+pub trait TripleSourceAsQuadSource: TripleSource + Sized {
+    /// Wrap this triple source into a quad source,
+    /// placing every triple in the default graph.
+    fn as_quad_source(self) -> InDefaultGraph<Self> {
+        InDefaultGraph(self)
+    }
+}
+impl<TS: TripleSource> TripleSourceAsQuadSource for TS {}
+
+/// The result of [`TripleSourceAsQuadSource::as_quad_source`](trait.TripleSourceAsQuadSource.html#method.as_quad_source).
+pub struct InDefaultGraph<TS>(TS);
+
+impl<TS: TripleSource> QuadSource for InDefaultGraph<TS> {
+    type Error = TS::Error;
+
+    fn in_sink<QS: QuadSink>(
+        &mut self,
+        sink: &mut QS,
+    ) -> Result<QS::Outcome, StreamError<Self::Error, QS::Error>> {
+        self.0.in_sink(&mut AsQuadSink(sink))
+    }
+}
+
+struct AsQuadSink<'a, QS>(&'a mut QS);
+
+impl<'a, QS: QuadSink> TripleSink for AsQuadSink<'a, QS> {
+    type Outcome = QS::Outcome;
+    type Error = QS::Error;
+
+    fn feed<T: Triple>(&mut self, t: &T) -> Result<(), Self::Error> {
+        self.0.feed(&DefaultGraphQuad(t))
+    }
+    fn finish(&mut self) -> Result<Self::Outcome, Self::Error> {
+        self.0.finish()
+    }
+}
+
+struct DefaultGraphQuad<'a, T>(&'a T);
+
+impl<'a, T: Triple> Quad for DefaultGraphQuad<'a, T> {
+    fn s(&self) -> &dyn TTerm {
+        self.0.s()
+    }
+    fn p(&self) -> &dyn TTerm {
+        self.0.p()
+    }
+    fn o(&self) -> &dyn TTerm {
+        self.0.o()
+    }
+    fn g(&self) -> Option<&dyn TTerm> {
+        None
+    }
+}
+
+/// Extends any [`QuadSource`](trait.QuadSource.html)
+/// with a method to view it as a [`TripleSource`](../../triple/stream/trait.TripleSource.html),
+/// dropping the graph name of every quad it yields.
+pub trait QuadSourceAsTripleSource: QuadSource + Sized {
+    /// Wrap this quad source into a triple source,
+    /// dropping the graph name of every quad.
+    fn as_triple_source(self) -> WithoutGraphName<Self> {
+        WithoutGraphName(self)
+    }
+}
+impl<QS: QuadSource> QuadSourceAsTripleSource for QS {}
+
+/// The result of [`QuadSourceAsTripleSource::as_triple_source`](trait.QuadSourceAsTripleSource.html#method.as_triple_source).
+pub struct WithoutGraphName<QS>(QS);
+
+impl<QS: QuadSource> TripleSource for WithoutGraphName<QS> {
+    type Error = QS::Error;
+
+    fn try_for_each_triple<F, E>(&mut self, mut f: F) -> StreamResult<(), Self::Error, E>
+    where
+        F: FnMut([&dyn TTerm; 3]) -> Result<(), E>,
+        E: 'static + Error,
+    {
+        self.0
+            .in_sink(&mut ClosureQuadSink(|q: &dyn Quad| f([q.s(), q.p(), q.o()])))
+    }
+}
+
+struct ClosureQuadSink<F>(F);
+
+impl<F, E> QuadSink for ClosureQuadSink<F>
+where
+    F: FnMut(&dyn Quad) -> Result<(), E>,
+    E: 'static + Error,
+{
+    type Outcome = ();
+    type Error = E;
+
+    fn feed<Q: Quad>(&mut self, q: &Q) -> Result<(), Self::Error> {
+        (self.0)(q)
+    }
+    fn finish(&mut self) -> Result<Self::Outcome, Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct TestTriple {
+        s: StaticTerm,
+        p: StaticTerm,
+        o: StaticTerm,
+    }
+
+    impl Triple for TestTriple {
+        fn s(&self) -> &dyn TTerm {
+            &self.s
+        }
+        fn p(&self) -> &dyn TTerm {
+            &self.p
+        }
+        fn o(&self) -> &dyn TTerm {
+            &self.o
+        }
+    }
+
+    struct TestQuad {
+        s: StaticTerm,
+        p: StaticTerm,
+        o: StaticTerm,
+        g: Option<StaticTerm>,
+    }
+
+    impl Quad for TestQuad {
+        fn s(&self) -> &dyn TTerm {
+            &self.s
+        }
+        fn p(&self) -> &dyn TTerm {
+            &self.p
+        }
+        fn o(&self) -> &dyn TTerm {
+            &self.o
+        }
+        fn g(&self) -> Option<&dyn TTerm> {
+            self.g.as_ref().map(|t| t as &dyn TTerm)
+        }
+    }
+
+    fn iri(suffix: &str) -> StaticTerm {
+        StaticTerm::new_iri(format!("http://example.org/{}", suffix)).unwrap()
+    }
+
+    #[test]
+    fn as_quad_source_feeds_every_quad_to_the_sink() {
+        let quads = vec![
+            TestQuad { s: iri("s1"), p: iri("p1"), o: iri("o1"), g: Some(iri("g1")) },
+            TestQuad { s: iri("s2"), p: iri("p2"), o: iri("o2"), g: None },
+        ];
+
+        let mut fed: Vec<(String, bool)> = Vec::new();
+        quads
+            .into_iter()
+            .as_quad_source()
+            .in_sink(&mut ClosureQuadSink(|q: &dyn Quad| -> Result<(), Infallible> {
+                fed.push((format!("{}", q.s()), q.g().is_some()));
+                Ok(())
+            }))
+            .unwrap();
+
+        assert_eq!(
+            fed,
+            vec![
+                ("<http://example.org/s1>".to_string(), true),
+                ("<http://example.org/s2>".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn triple_source_round_trips_through_default_graph_and_back() {
+        let triples = vec![Ok::<_, Infallible>(TestTriple { s: iri("s"), p: iri("p"), o: iri("o") })];
+
+        // TripleSource -> QuadSource (every triple lands in the default graph)...
+        let as_quads = triples.into_iter().as_quad_source();
+        // ...and back down to a TripleSource (the graph name is dropped again).
+        let mut back_to_triples = as_quads.as_triple_source();
+
+        let mut seen: Vec<String> = Vec::new();
+        back_to_triples.for_each_triple(|spo| {
+            seen.push(format!("{} {} {}", spo[0], spo[1], spo[2]));
+        });
+
+        assert_eq!(seen, vec!["<http://example.org/s> <http://example.org/p> <http://example.org/o>".to_string()]);
+    }
+}
@@ -0,0 +1,50 @@
+//! Serializing graphs through the [`TripleSink`] abstraction.
+//!
+//! Every serialization format exposes a `Config` type (one per format,
+//! living in that format's own module, e.g. [`nt::Config`]). `Default`
+//! picks that format's default configuration, `.writer(w)` builds a
+//! [`WriteSerializer`] that writes straight to an [`io::Write`], and
+//! `.stringifier()` builds a [`StringSerializer`] that accumulates the
+//! serialized output into a `String`. This is a convention each format
+//! follows on its own `Config` type rather than a shared trait (see the
+//! note below the two sink traits in this module for why).
+//!
+//! Because both are plain [`TripleSink`]s, a parse → transform → serialize
+//! pipeline is expressed entirely in terms of [`TripleSource`]/[`TripleSink`],
+//! with no special-casing for output:
+//!
+//! ```ignore
+//! nt::parse_str(src).in_sink(&mut nt::Config::default().writer(stdout))?;
+//! let ttl = nt::parse_str(src).in_sink(&mut nt::Config::default().stringifier())?;
+//! ```
+//!
+//! [`TripleSink`]: ../triple/stream/trait.TripleSink.html
+//! [`TripleSource`]: ../triple/stream/trait.TripleSource.html
+//! [`nt::Config`]: nt/struct.Config.html
+
+pub mod nt;
+
+use std::io;
+
+use crate::triple::stream::TripleSink;
+
+/// A [`TripleSink`](../triple/stream/trait.TripleSink.html) that serializes
+/// every triple it is fed straight to an [`io::Write`](https://doc.rust-lang.org/std/io/trait.Write.html).
+///
+/// It is implemented for every `TripleSink` whose outcome is `()` and for every `W`,
+/// so the bound alone does not guarantee that anything is written to a `W`.
+pub trait WriteSerializer<W: io::Write>: TripleSink<Outcome = ()> {}
+impl<W: io::Write, S> WriteSerializer<W> for S where S: TripleSink<Outcome = ()> {}
+
+/// A [`TripleSink`](../triple/stream/trait.TripleSink.html) that serializes
+/// every triple it is fed into an in-memory [`String`].
+///
+/// It is implemented for every `TripleSink` whose outcome is a `String`.
+pub trait StringSerializer: TripleSink<Outcome = String> {}
+impl<S> StringSerializer for S where S: TripleSink<Outcome = String> {}
+
+// `Config::writer`/`Config::stringifier` are not pulled into a shared
+// `SerializerConfig` trait: `.writer(w)` returns a type generic over
+// `W: io::Write`, which Rust cannot express as a trait method without
+// generic associated types, so each format's `Config` provides these
+// two methods directly instead (see `nt::Config` for N-Triples).
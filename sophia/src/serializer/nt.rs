@@ -0,0 +1,150 @@
+//! Serializing to the [N-Triples](https://www.w3.org/TR/n-triples/) format.
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::term::TTerm;
+use crate::triple::stream::TripleSink;
+use crate::triple::Triple;
+
+/// The configuration of the N-Triples serializer.
+///
+/// N-Triples has no variants worth configuring today;
+/// this struct exists so the format follows the same
+/// `Config::default().writer(...)` / `Config::default().stringifier()`
+/// convention as every other serializer.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Config {}
+
+impl Config {
+    /// Build a [`WriteSerializer`](../trait.WriteSerializer.html) writing N-Triples to `write`.
+    pub fn writer<W: io::Write>(self, write: W) -> Writer<W> {
+        Writer { write }
+    }
+
+    /// Build a [`StringSerializer`](../trait.StringSerializer.html) accumulating N-Triples into a `String`.
+    pub fn stringifier(self) -> Stringifier {
+        Stringifier { buffer: String::new() }
+    }
+}
+
+/// An N-Triples [`TripleSink`](../../triple/stream/trait.TripleSink.html)
+/// writing to an [`io::Write`](https://doc.rust-lang.org/std/io/trait.Write.html).
+///
+/// Built with [`Config::writer`](struct.Config.html#method.writer).
+pub struct Writer<W: io::Write> {
+    write: W,
+}
+
+impl<W: io::Write> TripleSink for Writer<W> {
+    type Outcome = ();
+    type Error = SerializerError;
+
+    fn feed<T: Triple>(&mut self, t: &T) -> Result<(), Self::Error> {
+        writeln!(
+            self.write,
+            "{} {} {} .",
+            write_term(t.s()),
+            write_term(t.p()),
+            write_term(t.o()),
+        )
+        .map_err(SerializerError)
+    }
+
+    fn finish(&mut self) -> Result<Self::Outcome, Self::Error> {
+        Ok(())
+    }
+}
+
+/// An N-Triples [`TripleSink`](../../triple/stream/trait.TripleSink.html)
+/// accumulating its output into a `String`.
+///
+/// Built with [`Config::stringifier`](struct.Config.html#method.stringifier).
+pub struct Stringifier {
+    buffer: String,
+}
+
+impl TripleSink for Stringifier {
+    type Outcome = String;
+    type Error = Infallible;
+
+    fn feed<T: Triple>(&mut self, t: &T) -> Result<(), Self::Error> {
+        self.buffer.push_str(&write_term(t.s()));
+        self.buffer.push(' ');
+        self.buffer.push_str(&write_term(t.p()));
+        self.buffer.push(' ');
+        self.buffer.push_str(&write_term(t.o()));
+        self.buffer.push_str(" .\n");
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<Self::Outcome, Self::Error> {
+        Ok(std::mem::take(&mut self.buffer))
+    }
+}
+
+/// The error type raised while writing N-Triples.
+#[derive(Debug)]
+pub struct SerializerError(io::Error);
+
+impl fmt::Display for SerializerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for SerializerError {}
+
+/// Render a term in N-Triples syntax.
+///
+/// Terms already know how to print themselves this way,
+/// so this just defers to their `Display` implementation.
+fn write_term(t: &dyn TTerm) -> String {
+    format!("{}", t)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::term::StaticTerm;
+
+    fn triple() -> (StaticTerm, StaticTerm, StaticTerm) {
+        let s = StaticTerm::new_iri("http://ex.co/data/a1").unwrap();
+        let p = StaticTerm::new_iri("http://ex.co/ns/value").unwrap();
+        let o = StaticTerm::new_literal_dt("42", StaticTerm::new_iri("http://www.w3.org/2001/XMLSchema#integer").unwrap()).unwrap();
+        (s, p, o)
+    }
+
+    #[test]
+    fn stringifier_renders_one_line_per_triple() {
+        let (s, p, o) = triple();
+
+        let mut sink = Config::default().stringifier();
+        sink.feed(&[&s as &dyn TTerm, &p as &dyn TTerm, &o as &dyn TTerm]).unwrap();
+        let out = sink.finish().unwrap();
+
+        assert_eq!(
+            out,
+            "<http://ex.co/data/a1> <http://ex.co/ns/value> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n",
+        );
+    }
+
+    #[test]
+    fn writer_writes_one_line_per_triple_to_the_given_writer() {
+        let (s, p, o) = triple();
+
+        let mut buf: Vec<u8> = Vec::new();
+        {
+            let mut sink = Config::default().writer(&mut buf);
+            sink.feed(&[&s as &dyn TTerm, &p as &dyn TTerm, &o as &dyn TTerm]).unwrap();
+            sink.finish().unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "<http://ex.co/data/a1> <http://ex.co/ns/value> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n",
+        );
+    }
+}
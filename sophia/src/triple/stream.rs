@@ -21,6 +21,13 @@
 //! *i.e.* references that will be valid during the time need to process them,
 //! but may be outlived by the triple source itself.
 //!
+//! This is why the *required* method of [`TripleSource`] is
+//! [`try_for_each_triple`](trait.TripleSource.html#tymethod.try_for_each_triple):
+//! it hands the subject, predicate and object of each triple to a closure,
+//! and is free to reuse (or drop) whatever it lent to that closure
+//! as soon as it returns.
+//! A parser can therefore keep a handful of reusable buffers around,
+//! overwrite them for each new triple, and still satisfy this trait.
 //!
 //! [`TripleSource`]: trait.TripleSource.html
 //! [`TripleSink`]: trait.TripleSink.html
@@ -35,6 +42,7 @@ use std::error::Error;
 use std::iter::Map;
 
 use crate::graph::*;
+use crate::term::*;
 use crate::triple::*;
 
 /// A triple source produces [triples], and may also fail in the process.
@@ -51,13 +59,47 @@ pub trait TripleSource {
     /// The type of errors produced by this source.
     type Error: 'static + Error;
 
+    /// Call `f` for each triple of this source,
+    /// passing its subject, predicate and object as short-lived references.
+    ///
+    /// Those references are only guaranteed to be valid for the duration of the call;
+    /// implementors are free to overwrite (or drop) whatever they lent to `f`
+    /// as soon as it returns, so `f` must not try to retain them.
+    ///
+    /// Stop on the first error (in the source, or raised by `f`).
+    fn try_for_each_triple<F, E>(&mut self, f: F) -> StreamResult<(), Self::Error, E>
+    where
+        F: FnMut([&dyn TTerm; 3]) -> Result<(), E>,
+        E: 'static + Error;
+
+    /// Call `f` for each triple of this source.
+    ///
+    /// # Panics
+    /// This method panics if the underlying source raises an error.
+    fn for_each_triple<F>(&mut self, mut f: F)
+    where
+        F: FnMut([&dyn TTerm; 3]),
+    {
+        match self.try_for_each_triple(|spo| -> Result<(), Infallible> {
+            f(spo);
+            Ok(())
+        }) {
+            Ok(()) => (),
+            Err(SourceError(err)) => panic!("{:?}", err),
+            Err(SinkError(_)) => unreachable!("the sink used by for_each_triple never fails"),
+        }
+    }
+
     /// Feed all triples from this source into the given [sink](trait.TripleSink.html).
     ///
     /// Stop on the first error (in the source or the sink).
     fn in_sink<TS: TripleSink>(
         &mut self,
         sink: &mut TS,
-    ) -> Result<TS::Outcome, StreamError<Self::Error, TS::Error>>;
+    ) -> Result<TS::Outcome, StreamError<Self::Error, TS::Error>> {
+        self.try_for_each_triple(|spo| sink.feed(&spo))?;
+        sink.finish().map_err(SinkError)
+    }
 
     /// Insert all triples from this source into the given [graph](../../graph/trait.MutableGraph.html).
     ///
@@ -68,8 +110,45 @@ pub trait TripleSource {
     ) -> Result<usize, StreamError<Self::Error, <G as MutableGraph>::MutationError>> {
         self.in_sink(&mut graph.inserter())
     }
+
+    /// Return a [`TripleSource`](trait.TripleSource.html)
+    /// that applies `f` to all triples of this source.
+    fn map_triples<F>(self, f: F) -> MapSource<Self, F>
+    where
+        Self: Sized,
+    {
+        MapSource { source: self, f }
+    }
+
+    /// Return a [`TripleSource`](trait.TripleSource.html)
+    /// that only keeps the triples of this source matching `f`.
+    fn filter_triples<F>(self, f: F) -> FilterSource<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&dyn Triple) -> bool,
+    {
+        FilterSource { source: self, predicate: f }
+    }
+
+    /// Consume this source, collecting its triples into a new `G`.
+    ///
+    /// This is a shortcut for [`CollectibleGraph::from_triple_source`](trait.CollectibleGraph.html#tymethod.from_triple_source).
+    fn collect_triples<G: CollectibleGraph>(self) -> StreamResult<G, Self::Error, G::Error>
+    where
+        Self: Sized,
+    {
+        G::from_triple_source(self)
+    }
 }
 
+/// A blanket implementation bridging plain iterators of (fallible) [`Triple`]s
+/// into the callback-based [`TripleSource`].
+///
+/// Because `T` is a long-lived item yielded by `self`,
+/// this implementation can simply borrow its subject, predicate and object for each call to `f`.
+///
+/// [`Triple`]: ../trait.Triple.html
+/// [`TripleSource`]: trait.TripleSource.html
 impl<I, T, E> TripleSource for I
 where
     I: Iterator<Item = Result<T, E>>,
@@ -78,15 +157,119 @@ where
 {
     type Error = E;
 
-    fn in_sink<TS: TripleSink>(
-        &mut self,
-        sink: &mut TS,
-    ) -> Result<TS::Outcome, StreamError<Self::Error, TS::Error>> {
+    fn try_for_each_triple<F, FErr>(&mut self, mut f: F) -> StreamResult<(), Self::Error, FErr>
+    where
+        F: FnMut([&dyn TTerm; 3]) -> Result<(), FErr>,
+        FErr: 'static + Error,
+    {
         for tr in self {
             let t = tr.map_err(SourceError)?;
-            sink.feed(&t).map_err(SinkError)?;
+            f([t.s(), t.p(), t.o()]).map_err(SinkError)?;
         }
-        Ok(sink.finish().map_err(SinkError)?)
+        Ok(())
+    }
+}
+
+/// The result of [`TripleSource::map_triples`](trait.TripleSource.html#method.map_triples).
+pub struct MapSource<S, F> {
+    source: S,
+    f: F,
+}
+
+impl<S, F, T> TripleSource for MapSource<S, F>
+where
+    S: TripleSource,
+    F: FnMut(&dyn Triple) -> T,
+    T: Triple,
+{
+    type Error = S::Error;
+
+    fn try_for_each_triple<F2, E>(&mut self, mut f2: F2) -> StreamResult<(), Self::Error, E>
+    where
+        F2: FnMut([&dyn TTerm; 3]) -> Result<(), E>,
+        E: 'static + Error,
+    {
+        let MapSource { source, f } = self;
+        source.try_for_each_triple(|spo| {
+            let mapped = f(&spo);
+            f2([mapped.s(), mapped.p(), mapped.o()])
+        })
+    }
+}
+
+/// The result of [`TripleSource::filter_triples`](trait.TripleSource.html#method.filter_triples).
+pub struct FilterSource<S, F> {
+    source: S,
+    predicate: F,
+}
+
+impl<S, F> TripleSource for FilterSource<S, F>
+where
+    S: TripleSource,
+    F: FnMut(&dyn Triple) -> bool,
+{
+    type Error = S::Error;
+
+    fn try_for_each_triple<F2, E>(&mut self, mut f2: F2) -> StreamResult<(), Self::Error, E>
+    where
+        F2: FnMut([&dyn TTerm; 3]) -> Result<(), E>,
+        E: 'static + Error,
+    {
+        let FilterSource { source, predicate } = self;
+        source.try_for_each_triple(|spo| {
+            if predicate(&spo) {
+                f2(spo)
+            } else {
+                Ok(())
+            }
+        })
+    }
+}
+
+/// `[&dyn TTerm; 3]` is itself a [`Triple`](../trait.Triple.html),
+/// which lets `try_for_each_triple`'s core implementations feed their
+/// borrowed subject/predicate/object straight into a [`TripleSink`](trait.TripleSink.html).
+impl<'a> Triple for [&'a dyn TTerm; 3] {
+    fn s(&self) -> &dyn TTerm {
+        self[0]
+    }
+    fn p(&self) -> &dyn TTerm {
+        self[1]
+    }
+    fn o(&self) -> &dyn TTerm {
+        self[2]
+    }
+}
+
+/// A graph that can be built directly from a [`TripleSource`](trait.TripleSource.html),
+/// without first constructing an empty instance and threading its mutation errors by hand.
+///
+/// See also [`TripleSource::collect_triples`](trait.TripleSource.html#method.collect_triples).
+pub trait CollectibleGraph: Sized {
+    /// The type of errors raised while building this graph.
+    type Error: 'static + Error;
+
+    /// Consume `src`, collecting all its triples into a new instance of `Self`.
+    fn from_triple_source<TS: TripleSource>(src: TS) -> StreamResult<Self, TS::Error, Self::Error>;
+}
+
+/// Any [`MutableGraph`](../../graph/trait.MutableGraph.html) that can be default-constructed
+/// is collectible: simply build an empty instance, then insert into it.
+///
+/// `Vec<[BoxTerm; 3]>` is itself a `MutableGraph`, so this single impl already
+/// covers both the "bare collection" case (`let v: Vec<[BoxTerm; 3]> = ...collect_triples()?`)
+/// and richer in-memory graphs; it is not (and must not be) duplicated by a
+/// dedicated `Vec` impl, which would conflict with this one.
+impl<G> CollectibleGraph for G
+where
+    G: MutableGraph + Default,
+{
+    type Error = <G as MutableGraph>::MutationError;
+
+    fn from_triple_source<TS: TripleSource>(mut src: TS) -> StreamResult<Self, TS::Error, Self::Error> {
+        let mut g = G::default();
+        src.in_graph(&mut g)?;
+        Ok(g)
     }
 }
 
@@ -0,0 +1,41 @@
+//! Regression test for `#[derive(FromGraph)]`'s `Vec<T>` cardinality
+//! (the `build_many` codegen path in `sophia_derive`).
+
+use ::sophia::convert::FromGraph;
+use ::sophia::graph::inmem::FastGraph;
+use ::sophia::parsers::nt;
+use ::sophia::term::StaticTerm;
+use ::sophia::triple::stream::TripleSource;
+use ::sophia_derive::FromGraph;
+
+#[derive(Debug, Clone, FromGraph)]
+struct Item {
+    #[rdf(predicate = "http://www.w3.org/1999/02/22-rdf-syntax-ns#value")]
+    value: i32,
+}
+
+#[derive(Debug, Clone, FromGraph)]
+struct Bag {
+    #[rdf(predicate = "http://ex.co/ns/has_item", nested)]
+    items: Vec<Item>,
+}
+
+static SRC: &str = r#"
+<http://ex.co/data/bag1> <http://ex.co/ns/has_item> <http://ex.co/data/i1>.
+<http://ex.co/data/bag1> <http://ex.co/ns/has_item> <http://ex.co/data/i2>.
+<http://ex.co/data/i1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#value> "1"^^<http://www.w3.org/2001/XMLSchema#integer>.
+<http://ex.co/data/i2> <http://www.w3.org/1999/02/22-rdf-syntax-ns#value> "2"^^<http://www.w3.org/2001/XMLSchema#integer>.
+"#;
+
+#[test]
+fn vec_field_collects_every_matching_triple() {
+    let mut g = FastGraph::new();
+    nt::parse_str(SRC).in_graph(&mut g).unwrap();
+
+    let subject = StaticTerm::new_iri("http://ex.co/data/bag1").unwrap();
+    let bag = Bag::from_graph(&subject, &g).unwrap();
+
+    let mut values: Vec<i32> = bag.items.iter().map(|i| i.value).collect();
+    values.sort_unstable();
+    assert_eq!(values, vec![1, 2]);
+}
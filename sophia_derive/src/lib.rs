@@ -0,0 +1,179 @@
+//! `#[derive(FromGraph)]`: generates a [`FromGraph`] implementation from
+//! `#[rdf(predicate = "...")]` field attributes.
+//!
+//! See the [`convert`](../sophia/convert/index.html) module of the `sophia`
+//! crate for the trait this macro implements, and for the supported attributes.
+//!
+//! [`FromGraph`]: ../sophia/convert/trait.FromGraph.html
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, PathArguments, Type};
+
+#[proc_macro_derive(FromGraph, attributes(rdf))]
+pub fn derive_from_graph(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => panic!("#[derive(FromGraph)] only supports structs with named fields"),
+        },
+        _ => panic!("#[derive(FromGraph)] only supports structs"),
+    };
+
+    let field_inits = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().expect("named field");
+        let predicate = rdf_predicate(field);
+        let nested = is_nested(field);
+
+        match cardinality(&field.ty) {
+            Cardinality::One => build_one(ident, &predicate, nested),
+            Cardinality::Optional => build_optional(ident, &predicate, nested),
+            Cardinality::Many => build_many(ident, &predicate, nested),
+        }
+    });
+
+    let expanded = quote! {
+        impl<Td, E> ::sophia::convert::FromGraph<Td, E> for #name
+        where
+            Td: ::std::borrow::Borrow<str>,
+            E: ::sophia::convert::GraphError,
+        {
+            fn from_graph<G>(
+                subject: &::sophia::term::Term<Td>,
+                graph: &G,
+            ) -> Result<Self, ::sophia::convert::FromGraphError>
+            where
+                G: ::sophia::convert::OwnedGraph<E>,
+            {
+                Ok(#name {
+                    #(#field_inits),*
+                })
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+enum Cardinality {
+    One,
+    Optional,
+    Many,
+}
+
+/// Classify a field's declared type as `Option<T>`, `Vec<T>`, or a bare `T`,
+/// so the same value-extraction code can be reused for all three cardinalities.
+fn cardinality(ty: &Type) -> Cardinality {
+    if is_wrapped_in(ty, "Option") {
+        Cardinality::Optional
+    } else if is_wrapped_in(ty, "Vec") {
+        Cardinality::Many
+    } else {
+        Cardinality::One
+    }
+}
+
+fn is_wrapped_in(ty: &Type, wrapper: &str) -> bool {
+    let path = match ty {
+        Type::Path(p) => &p.path,
+        _ => return false,
+    };
+    let segment = match path.segments.last() {
+        Some(s) => s,
+        None => return false,
+    };
+    segment.ident == wrapper && matches!(segment.arguments, PathArguments::AngleBracketed(_))
+}
+
+fn rdf_predicate(field: &syn::Field) -> String {
+    for attr in &field.attrs {
+        if !attr.path.is_ident("rdf") {
+            continue;
+        }
+        if let Ok(syn::Meta::List(list)) = attr.parse_meta() {
+            for nested in list.nested {
+                if let syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) = nested {
+                    if nv.path.is_ident("predicate") {
+                        if let syn::Lit::Str(s) = nv.lit {
+                            return s.value();
+                        }
+                    }
+                }
+            }
+        }
+    }
+    panic!("field is missing #[rdf(predicate = \"...\")]");
+}
+
+fn is_nested(field: &syn::Field) -> bool {
+    for attr in &field.attrs {
+        if !attr.path.is_ident("rdf") {
+            continue;
+        }
+        if let Ok(syn::Meta::List(list)) = attr.parse_meta() {
+            for nested in list.nested {
+                if let syn::NestedMeta::Meta(syn::Meta::Path(p)) = nested {
+                    if p.is_ident("nested") {
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+fn build_one(ident: &Ident, predicate: &str, nested: bool) -> proc_macro2::TokenStream {
+    let value = extract_one(nested, &syn::parse_str::<syn::Expr>("__t.o()").unwrap());
+    quote! {
+        #ident: {
+            let __p = ::sophia::term::StaticTerm::new_iri(#predicate).unwrap();
+            let __t = ::sophia::convert::required(graph.iter_for_sp(subject, &__p).next(), #predicate)?;
+            #value
+        }
+    }
+}
+
+fn build_optional(ident: &Ident, predicate: &str, nested: bool) -> proc_macro2::TokenStream {
+    let value = extract_one(nested, &syn::parse_str::<syn::Expr>("__t.o()").unwrap());
+    quote! {
+        #ident: {
+            let __p = ::sophia::term::StaticTerm::new_iri(#predicate).unwrap();
+            match graph.iter_for_sp(subject, &__p).next() {
+                None => None,
+                Some(__t) => {
+                    let __t = ::sophia::convert::required(Some(__t), #predicate)?;
+                    Some(#value)
+                }
+            }
+        }
+    }
+}
+
+fn build_many(ident: &Ident, predicate: &str, nested: bool) -> proc_macro2::TokenStream {
+    let value = extract_one(nested, &syn::parse_str::<syn::Expr>("__t.o()").unwrap());
+    quote! {
+        #ident: {
+            let __p = ::sophia::term::StaticTerm::new_iri(#predicate).unwrap();
+            ::sophia::convert::many(graph.iter_for_sp(subject, &__p))
+                .map(|__t| {
+                    let __t = __t?;
+                    Ok(#value)
+                })
+                .collect::<Result<_, ::sophia::convert::FromGraphError>>()?
+        }
+    }
+}
+
+fn extract_one(nested: bool, object: &syn::Expr) -> proc_macro2::TokenStream {
+    if nested {
+        quote! { ::sophia::convert::FromGraph::from_graph(#object, graph)? }
+    } else {
+        quote! { ::sophia::convert::FromGraphTerm::from_graph_term(#object)? }
+    }
+}